@@ -1,37 +1,338 @@
 use futures::channel::mpsc;
+use futures::StreamExt;
 use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
-use tokio::sync::RwLock;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::sync::{watch, RwLock};
 use tokio::{runtime::Runtime, time};
 use zksync_storage::ConnectionPool;
 use zksync_types::BlockNumber;
 use zksync_utils::panic_notify::ThreadPanicNotify;
 
-#[derive(Default, Debug, Serialize, Deserialize, Clone)]
+#[derive(Default, Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct NetworkStatus {
+    /// Monotonically increasing counter bumped on every genuinely-new status.
+    /// Clients echo it back via `read_changed_since` to long-poll for changes.
+    pub version: u64,
+    /// Upper-bound ETA for the next block as a Unix timestamp in seconds
+    /// (not a block count or millis), derived from observed block cadence.
     pub next_block_at_max: Option<u64>,
     pub last_committed: BlockNumber,
     pub last_verified: BlockNumber,
     pub total_transactions: u32,
     pub outstanding_txs: u32,
     pub mempool_size: u32,
+    /// `true` when the background updater hasn't refreshed recently, so the
+    /// figures above may be out of date. Populated at `read()` time from the
+    /// health bookkeeping (it is never stored or broadcast).
+    pub stale: bool,
 }
 
-#[derive(Debug, Default, Clone)]
-pub struct SharedNetworkStatus(Arc<RwLock<NetworkStatus>>);
+#[derive(Debug, Clone)]
+pub struct SharedNetworkStatus {
+    status: Arc<RwLock<NetworkStatus>>,
+    // Fans every genuinely-new status out to subscribers so REST handlers can
+    // await changes instead of re-reading the lock on a timer. Mirrors the
+    // mempool-event broadcast: the updater is the sole producer.
+    status_sender: Arc<watch::Sender<NetworkStatus>>,
+    // When set, the independent storage reads are run concurrently over
+    // several connections at the cost of a single-transaction snapshot. Behind
+    // an `Arc` so toggling it on one clone reaches the updater's clone too.
+    parallel_reads: Arc<AtomicBool>,
+    // Tracks observed block-production cadence to estimate the next block ETA.
+    block_timing: Arc<RwLock<BlockTimingTracker>>,
+    // Freshness bookkeeping so callers can tell when the status went stale.
+    health: Arc<RwLock<HealthState>>,
+}
+
+/// Freshness bookkeeping for the background updater: when the last successful
+/// refresh happened and how many refreshes have failed back-to-back since.
+#[derive(Debug, Clone, Copy)]
+pub struct HealthState {
+    pub last_successful_update: Instant,
+    pub consecutive_failures: u32,
+}
+
+/// Default number of inter-block intervals averaged for the next-block ETA.
+const DEFAULT_BLOCK_TIMING_WINDOW: usize = 10;
+
+/// Age past which `read()` flags the status as stale (three missed ticks of
+/// the 30s base refresh interval).
+const DEFAULT_STATUS_MAX_AGE: Duration = Duration::from_secs(90);
+
+impl Default for SharedNetworkStatus {
+    fn default() -> Self {
+        let status = NetworkStatus::default();
+        let (status_sender, _) = watch::channel(status.clone());
+        Self {
+            status: Arc::new(RwLock::new(status)),
+            status_sender: Arc::new(status_sender),
+            parallel_reads: Arc::new(AtomicBool::new(false)),
+            block_timing: Arc::new(RwLock::new(BlockTimingTracker::new(
+                DEFAULT_BLOCK_TIMING_WINDOW,
+            ))),
+            health: Arc::new(RwLock::new(HealthState {
+                last_successful_update: Instant::now(),
+                consecutive_failures: 0,
+            })),
+        }
+    }
+}
+
+/// Keeps a small ring buffer of the times at which new committed blocks were
+/// observed and derives a moving-average inter-block interval from them.
+struct BlockTimingTracker {
+    /// Number of intervals to average over; the buffer holds `window + 1` times.
+    window: usize,
+    last_committed: Option<BlockNumber>,
+    /// Unix timestamps (seconds) at which `last_committed` was seen to advance.
+    samples: VecDeque<u64>,
+}
+
+impl BlockTimingTracker {
+    fn new(window: usize) -> Self {
+        Self {
+            window: window.max(1),
+            last_committed: None,
+            samples: VecDeque::with_capacity(window + 1),
+        }
+    }
+
+    /// Records the current committed block at time `now` and returns the
+    /// upper-bound ETA (`last observation + average interval`) for the next
+    /// block, or `None` while fewer than two samples have accumulated.
+    fn observe(&mut self, last_committed: BlockNumber, now: u64) -> Option<u64> {
+        let advanced = match self.last_committed {
+            Some(prev) => last_committed > prev,
+            None => true,
+        };
+        if advanced {
+            self.last_committed = Some(last_committed);
+            self.samples.push_back(now);
+            while self.samples.len() > self.window + 1 {
+                self.samples.pop_front();
+            }
+        }
+
+        if self.samples.len() >= 2 {
+            let first = *self.samples.front().unwrap();
+            let last = *self.samples.back().unwrap();
+            // Saturate: an NTP step-back or VM pause/resume can make a later
+            // sample smaller than an earlier one, which would otherwise
+            // underflow/overflow into a garbage ETA.
+            let average_interval = last.saturating_sub(first) / (self.samples.len() as u64 - 1);
+            Some(last.saturating_add(average_interval))
+        } else {
+            None
+        }
+    }
+}
+
+/// Current wall-clock time as a Unix timestamp in seconds.
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// The raw per-refresh figures read from storage, before they are assembled
+/// into a published [`NetworkStatus`].
+struct StatusSnapshot {
+    last_verified: BlockNumber,
+    last_committed: BlockNumber,
+    total_transactions: u32,
+    mempool_size: u32,
+    outstanding_txs: u32,
+}
 
 impl SharedNetworkStatus {
     pub async fn read(&self) -> NetworkStatus {
-        (*self.0.as_ref().read().await).clone()
+        let mut status = (*self.status.as_ref().read().await).clone();
+        // Surface staleness inline so a client hitting `/network_status` can
+        // tell the data is old without a separate `health()` call.
+        status.stale = !self.is_healthy(DEFAULT_STATUS_MAX_AGE).await;
+        status
+    }
+
+    /// Returns the current freshness bookkeeping for the background updater.
+    pub async fn health(&self) -> HealthState {
+        *self.health.as_ref().read().await
+    }
+
+    /// Returns `true` while the last successful refresh is within `max_age`.
+    /// The REST layer uses this to serve a degraded/503-style signal instead
+    /// of confidently returning stale numbers.
+    pub async fn is_healthy(&self, max_age: Duration) -> bool {
+        self.health.as_ref().read().await.last_successful_update.elapsed() <= max_age
+    }
+
+    /// Records a successful refresh, clearing the failure streak.
+    async fn mark_updated(&self) {
+        let mut health = self.health.as_ref().write().await;
+        health.last_successful_update = Instant::now();
+        health.consecutive_failures = 0;
+    }
+
+    /// Records a failed refresh and returns the new consecutive-failure count.
+    async fn mark_failed(&self) -> u32 {
+        let mut health = self.health.as_ref().write().await;
+        health.consecutive_failures += 1;
+        health.consecutive_failures
+    }
+
+    /// Runs a single refresh, updating the health bookkeeping, and returns the
+    /// interval to wait before the next periodic refresh: the base cadence on
+    /// success, or twice the current interval (capped) on failure.
+    async fn refresh_once(
+        &mut self,
+        connection_pool: &ConnectionPool,
+        interval: Duration,
+        base_interval: Duration,
+        max_interval: Duration,
+    ) -> Duration {
+        match self.update(connection_pool).await {
+            Ok(()) => {
+                self.mark_updated().await;
+                base_interval
+            }
+            Err(err) => {
+                let failures = self.mark_failed().await;
+                vlog::error!(
+                    "Can't update network status (failure #{}): {}",
+                    failures,
+                    err
+                );
+                (interval * 2).min(max_interval)
+            }
+        }
+    }
+
+    /// Returns a receiver that is notified whenever the published status
+    /// actually changes, letting callers stream updates without polling.
+    pub fn subscribe(&self) -> watch::Receiver<NetworkStatus> {
+        self.status_sender.subscribe()
+    }
+
+    /// Long-poll for a newer status than the one the caller last observed.
+    ///
+    /// Parks the request until the stored `version` exceeds `last_version` or
+    /// `timeout` elapses, then returns the fresh status (or `None` on timeout).
+    /// This lets clients get near-instant notification of new committed/verified
+    /// blocks without busy-polling `/network_status`.
+    pub async fn read_changed_since(
+        &self,
+        last_version: u64,
+        timeout: Duration,
+    ) -> Option<NetworkStatus> {
+        // Subscribe *before* the fast-path read so an update published in the
+        // gap between the two can't be marked "seen" and swallowed, which would
+        // block the caller for the full timeout despite a fresher version.
+        let mut receiver = self.subscribe();
+        {
+            let current = self.status.as_ref().read().await;
+            if current.version > last_version {
+                return Some(current.clone());
+            }
+        }
+
+        let wait = async {
+            while receiver.changed().await.is_ok() {
+                let status = receiver.borrow().clone();
+                if status.version > last_version {
+                    return Some(status);
+                }
+            }
+            // Sender dropped: no further updates will arrive.
+            None
+        };
+
+        match time::timeout(timeout, wait).await {
+            Ok(status) => status,
+            Err(_) => None,
+        }
+    }
+
+    /// Enables fanning the independent storage reads out over several
+    /// connections instead of issuing them sequentially in one transaction.
+    ///
+    /// The parallel snapshot is only eventually-consistent across connections;
+    /// callers that need a single-transaction snapshot should leave this off.
+    pub fn set_parallel_reads(&self, enabled: bool) {
+        self.parallel_reads.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Sets how many inter-block intervals are averaged for the next-block ETA.
+    pub async fn set_block_timing_window(&self, window: usize) {
+        *self.block_timing.write().await = BlockTimingTracker::new(window);
     }
 
     pub(crate) async fn update(
         &mut self,
         connection_pool: &ConnectionPool,
     ) -> Result<(), anyhow::Error> {
-        let mut storage = connection_pool.access_storage().await?;
+        let StatusSnapshot {
+            last_verified,
+            last_committed,
+            total_transactions,
+            mempool_size,
+            outstanding_txs,
+        } = if self.parallel_reads.load(Ordering::Relaxed) {
+            Self::collect_parallel(connection_pool).await?
+        } else {
+            Self::collect_sequential(connection_pool).await?
+        };
+
+        // Upper-bound ETA for the next block, derived from observed cadence.
+        let next_block_at_max = self
+            .block_timing
+            .write()
+            .await
+            .observe(last_committed, now_secs());
+
+        let mut status = NetworkStatus {
+            version: 0,
+            next_block_at_max,
+            last_committed,
+            last_verified,
+            total_transactions,
+            outstanding_txs,
+            mempool_size,
+            // Staleness is derived at `read()` time, never stored or broadcast.
+            stale: false,
+        };
 
+        // Compare content ignoring the version counter: align the candidate's
+        // version with the currently stored one so equal content compares equal.
+        let current_version = self.status.as_ref().read().await.version;
+        status.version = current_version;
+        let changed = *self.status.as_ref().read().await != status;
+
+        if changed {
+            // Bump the version so long-pollers awaiting a newer snapshot wake up.
+            status.version = current_version + 1;
+        }
+
+        // save status to state
+        *self.status.as_ref().write().await = status.clone();
+
+        if changed {
+            // A send error only means there are no live receivers. The channel
+            // then keeps its previous value, so `read_changed_since` checks the
+            // stored status rather than the channel's for its fast path.
+            let _ = self.status_sender.send(status);
+        }
+        Ok(())
+    }
+
+    /// Reads all figures sequentially inside a single transaction, giving a
+    /// consistent snapshot at the cost of summing five round-trips.
+    async fn collect_sequential(
+        connection_pool: &ConnectionPool,
+    ) -> Result<StatusSnapshot, anyhow::Error> {
+        let mut storage = connection_pool.access_storage().await?;
         let mut transaction = storage.start_transaction().await?;
 
         let last_verified = transaction
@@ -69,26 +370,108 @@ impl SharedNetworkStatus {
             .await
             .unwrap_or(0);
 
-        let status = NetworkStatus {
-            next_block_at_max: None,
-            last_committed,
+        transaction.commit().await.unwrap_or_default();
+
+        Ok(StatusSnapshot {
             last_verified,
+            last_committed,
             total_transactions,
-            outstanding_txs,
             mempool_size,
+            outstanding_txs,
+        })
+    }
+
+    /// Reads the independent figures concurrently over separate connections,
+    /// then resolves `outstanding_txs` once `last_verified` is known. The
+    /// resulting snapshot is only eventually-consistent across connections.
+    async fn collect_parallel(
+        connection_pool: &ConnectionPool,
+    ) -> Result<StatusSnapshot, anyhow::Error> {
+        let last_verified_fut = async {
+            let mut storage = connection_pool.access_storage().await?;
+            Ok::<_, anyhow::Error>(
+                storage
+                    .chain()
+                    .block_schema()
+                    .get_last_verified_confirmed_block()
+                    .await
+                    .unwrap_or(BlockNumber(0)),
+            )
+        };
+        let last_committed_fut = async {
+            let mut storage = connection_pool.access_storage().await?;
+            Ok::<_, anyhow::Error>(
+                storage
+                    .chain()
+                    .block_schema()
+                    .get_last_committed_block()
+                    .await
+                    .unwrap_or(BlockNumber(0)),
+            )
+        };
+        let total_transactions_fut = async {
+            let mut storage = connection_pool.access_storage().await?;
+            Ok::<_, anyhow::Error>(
+                storage
+                    .chain()
+                    .stats_schema()
+                    .count_total_transactions()
+                    .await
+                    .unwrap_or(0),
+            )
+        };
+        let mempool_size_fut = async {
+            let mut storage = connection_pool.access_storage().await?;
+            Ok::<_, anyhow::Error>(
+                storage
+                    .chain()
+                    .mempool_schema()
+                    .get_mempool_size()
+                    .await
+                    .unwrap_or(0),
+            )
         };
 
-        transaction.commit().await.unwrap_or_default();
+        let (last_verified, last_committed, total_transactions, mempool_size) = futures::try_join!(
+            last_verified_fut,
+            last_committed_fut,
+            total_transactions_fut,
+            mempool_size_fut
+        )?;
 
-        // save status to state
-        *self.0.as_ref().write().await = status;
-        Ok(())
+        // `outstanding_txs` depends on `last_verified`, so it runs afterwards.
+        let outstanding_txs = {
+            let mut storage = connection_pool.access_storage().await?;
+            storage
+                .chain()
+                .stats_schema()
+                .count_outstanding_proofs(last_verified)
+                .await
+                .unwrap_or(0)
+        };
+
+        Ok(StatusSnapshot {
+            last_verified,
+            last_committed,
+            total_transactions,
+            mempool_size,
+            outstanding_txs,
+        })
     }
+
+    /// Spawns the background updater thread and returns a sender that other
+    /// subsystems (the mempool, the block committer) use to signal that the
+    /// status is "dirty" — e.g. right after inserting transactions or
+    /// committing a block. The updater coalesces a burst of such signals into
+    /// at most one refresh per debounce window, keeping `/network_status`
+    /// almost immediately fresh without dropping the periodic fallback.
     pub fn start_updater_detached(
         mut self,
         panic_notify: mpsc::Sender<bool>,
-        mut connection_pool: ConnectionPool,
-    ) {
+        connection_pool: ConnectionPool,
+    ) -> mpsc::Sender<()> {
+        let (dirty_sender, mut dirty_receiver) = mpsc::channel(1);
+
         std::thread::Builder::new()
             .name("rest-state-updater".to_string())
             .spawn(move || {
@@ -97,16 +480,43 @@ impl SharedNetworkStatus {
                 let runtime = Runtime::new().expect("tokio runtime creation");
 
                 let state_update_task = async move {
-                    let mut timer = time::interval(Duration::from_millis(30000));
+                    // Base cadence plus exponential backoff so a prolonged DB
+                    // outage doesn't hammer the pool every 30s; the interval
+                    // snaps back to the base as soon as a refresh succeeds.
+                    let base_interval = Duration::from_millis(30000);
+                    let max_interval = Duration::from_secs(300);
+                    // Window over which a burst of dirty signals is coalesced.
+                    let debounce = Duration::from_millis(200);
+                    let mut interval = base_interval;
+                    // Once every dirty sender is dropped we rely on the timer.
+                    let mut dirty_closed = false;
+
                     loop {
-                        timer.tick().await;
-                        if let Err(_) = self.update(&mut connection_pool).await {
-                            vlog::error!("Can't update network status")
+                        tokio::select! {
+                            _ = time::sleep(interval) => {}
+                            signal = dirty_receiver.next(), if !dirty_closed => {
+                                if signal.is_none() {
+                                    // All senders gone: fall back to periodic.
+                                    dirty_closed = true;
+                                    continue;
+                                }
+                                // Coalesce the burst: wait out the debounce
+                                // window and drain any further signals so a
+                                // flood of new txs triggers a single refresh.
+                                time::sleep(debounce).await;
+                                while let Ok(Some(_)) = dirty_receiver.try_next() {}
+                            }
                         }
+
+                        interval = self
+                            .refresh_once(&connection_pool, interval, base_interval, max_interval)
+                            .await;
                     }
                 };
                 runtime.block_on(state_update_task);
             })
             .expect("State update thread");
+
+        dirty_sender
     }
 }